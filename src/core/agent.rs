@@ -0,0 +1,105 @@
+//! Agent Configuration
+//!
+//! The editor-agnostic definition of an agent. Installers read this struct and
+//! translate it into whatever format their target editor expects.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// A single agent definition, shared across every install target
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentConfig {
+    /// Unique agent name, used to namespace every installed artifact
+    pub name: String,
+    /// Short, human-readable description of what the agent does
+    pub description: String,
+    /// System prompt, icon, model, and sampling configuration
+    pub identity: AgentIdentity,
+    /// Knowledge-base skills bundled with the agent
+    #[serde(default)]
+    pub skills: Vec<Skill>,
+    /// MCP tools the agent wants registered with the editor
+    #[serde(default)]
+    pub mcp: Vec<McpTool>,
+    /// Placeholders referenced as `{{name}}` in the system prompt, skill
+    /// bodies, and MCP `env` values
+    #[serde(default)]
+    pub variables: Vec<Variable>,
+    /// Retrieval knowledge-base source documents
+    #[serde(default)]
+    pub rag: Vec<RagSource>,
+}
+
+/// The agent's identity: what it is and how it should sample
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentIdentity {
+    pub system_prompt: String,
+    #[serde(default)]
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Sampling/model overrides; a target only serializes the subset it supports
+    #[serde(default)]
+    pub parameters: Option<Parameters>,
+}
+
+/// Per-agent sampling and model overrides. Any field left `None` is omitted
+/// entirely from generated output so the editor's own default wins.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Parameters {
+    #[serde(default)]
+    pub temperature: Option<f64>,
+    #[serde(default)]
+    pub top_p: Option<f64>,
+    #[serde(default)]
+    pub reasoning_effort: Option<String>,
+    #[serde(default)]
+    pub max_tokens: Option<u32>,
+    /// Per-target model override, takes precedence over `AgentIdentity::model`
+    #[serde(default)]
+    pub model_id: Option<String>,
+}
+
+/// A knowledge-base skill: a named chunk of markdown shipped with the agent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Skill {
+    pub name: String,
+    pub content: String,
+}
+
+/// An MCP tool the agent wants registered with the editor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpTool {
+    pub name: String,
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// URL to walk the user through obtaining credentials for this tool, if any
+    #[serde(default)]
+    pub setup_url: Option<String>,
+}
+
+/// A placeholder an agent declares so `{{name}}` can be substituted at install
+/// time instead of hardcoded in the source definition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Variable {
+    pub name: String,
+    pub description: String,
+    #[serde(default)]
+    pub default: Option<String>,
+}
+
+/// A RAG source document: a local path or glob, plus optional hints for how it
+/// should be chunked and embedded
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RagSource {
+    /// Local file path, or a single-level `*` glob
+    pub path: String,
+    #[serde(default)]
+    pub chunk_size: Option<usize>,
+    #[serde(default)]
+    pub embedding_model: Option<String>,
+}
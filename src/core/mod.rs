@@ -0,0 +1,6 @@
+//! Core Module
+//!
+//! Data types shared across the installers: the agent definition itself and
+//! everything an `Installer` reads off of it.
+
+pub mod agent;
@@ -5,6 +5,7 @@
 //! Output structure:
 //! - ~/.codex/agents/{name}.md - Agent as Markdown with YAML frontmatter
 //! - ~/.codex/skills/{name}/{skill}.md - Skills as Markdown files
+//! - ~/.codex/rag/{name}/ - RAG source documents plus a rag.json manifest
 //! - ~/.codex/config.json - MCP tool configuration (assumed)
 
 use anyhow::{Context, Result};
@@ -13,7 +14,8 @@ use colored::Colorize;
 use std::fs;
 use std::path::PathBuf;
 
-use super::Installer;
+use super::{rag, tools, variables};
+use super::{Installer, PlannedFile};
 use crate::core::agent::AgentConfig;
 use crate::utils::paths;
 
@@ -44,105 +46,159 @@ impl CodexInstaller {
         Ok(self.get_base_dir()?.join("config.json"))
     }
 
-    /// Generate the markdown content with YAML frontmatter
-    fn generate_agent_markdown(agent: &AgentConfig) -> String {
-        let icon = agent.identity.icon.as_deref().unwrap_or("🤖");
-        let model = agent.identity.model.as_deref().unwrap_or("gpt-4o");
-        
-        format!(
-            r#"---
-name: {}
-description: {}
-model: {}
-icon: {}
----
-
-{}"#,
-            agent.name,
-            agent.description,
-            model,
-            icon,
-            agent.identity.system_prompt
-        )
+    /// Get the path to an agent's persisted variable answers
+    fn get_vars_file(&self, agent_name: &str) -> Result<PathBuf> {
+        Ok(variables::vars_file_path(&self.get_agents_dir()?, agent_name))
     }
-}
 
-impl Installer for CodexInstaller {
-    fn install_identity(&self, agent: &AgentConfig) -> Result<()> {
-        let agents_dir = self.get_agents_dir()?;
-        fs::create_dir_all(&agents_dir)?;
+    /// Get the RAG store directory for an agent
+    fn get_rag_dir(&self, agent_name: &str) -> Result<PathBuf> {
+        Ok(self.get_base_dir()?.join("rag").join(agent_name))
+    }
 
-        // Create the agent markdown file
-        let agent_file = agents_dir.join(format!("{}.md", agent.name));
-        let markdown_content = Self::generate_agent_markdown(agent);
-        
-        fs::write(&agent_file, markdown_content)?;
+    /// Generate the markdown content with YAML frontmatter
+    fn generate_agent_markdown(agent: &AgentConfig, system_prompt: &str) -> String {
+        let icon = agent.identity.icon.as_deref().unwrap_or("🤖");
+        let params = agent.identity.parameters.as_ref();
+        let model = params
+            .and_then(|p| p.model_id.as_deref())
+            .or(agent.identity.model.as_deref())
+            .unwrap_or("gpt-4o");
+
+        let mut frontmatter = format!(
+            "name: {}\ndescription: {}\nmodel: {}\nicon: {}",
+            agent.name, agent.description, model, icon
+        );
+
+        // Only emit sampling keys the user actually set, so Codex's own defaults
+        // apply to anything left `None`
+        if let Some(params) = params {
+            if let Some(temperature) = params.temperature {
+                frontmatter.push_str(&format!("\ntemperature: {}", temperature));
+            }
+            if let Some(top_p) = params.top_p {
+                frontmatter.push_str(&format!("\ntop_p: {}", top_p));
+            }
+            if let Some(reasoning_effort) = &params.reasoning_effort {
+                frontmatter.push_str(&format!("\nreasoning_effort: {}", reasoning_effort));
+            }
+            if let Some(max_tokens) = params.max_tokens {
+                frontmatter.push_str(&format!("\nmax_tokens: {}", max_tokens));
+            }
+        }
 
-        Ok(())
+        format!("---\n{}\n---\n\n{}", frontmatter, system_prompt)
     }
 
-    fn install_skills(&self, agent: &AgentConfig) -> Result<()> {
-        if agent.skills.is_empty() {
-            return Ok(());
-        }
+    /// Render the agent markdown file's path and final (variable-substituted) content
+    fn render_identity(&self, agent: &AgentConfig) -> Result<(PathBuf, String)> {
+        let vars_file = self.get_vars_file(&agent.name)?;
+        let values = variables::resolve(agent, &[&agent.identity.system_prompt], &vars_file)?;
+        let system_prompt = variables::substitute(&agent.identity.system_prompt, &values)
+            .context("Failed to render agent system prompt")?;
 
-        let base_dir = self.get_base_dir()?;
-        let skills_dir = base_dir.join("skills").join(&agent.name);
-        fs::create_dir_all(&skills_dir)?;
+        let agent_file = self.get_agents_dir()?.join(format!("{}.md", agent.name));
+        Ok((agent_file, Self::generate_agent_markdown(agent, &system_prompt)))
+    }
 
-        for skill in &agent.skills {
-            let skill_file = skills_dir.join(format!("{}.md", skill.name));
-            fs::write(&skill_file, &skill.content)?;
+    /// Render each skill file's path and final (variable-substituted) content
+    fn render_skills(&self, agent: &AgentConfig) -> Result<Vec<(PathBuf, String)>> {
+        if agent.skills.is_empty() {
+            return Ok(Vec::new());
         }
 
-        Ok(())
+        let skills_dir = self.get_base_dir()?.join("skills").join(&agent.name);
+        let vars_file = self.get_vars_file(&agent.name)?;
+        let skill_texts: Vec<&str> = agent.skills.iter().map(|s| s.content.as_str()).collect();
+        let values = variables::resolve(agent, &skill_texts, &vars_file)?;
+
+        agent
+            .skills
+            .iter()
+            .map(|skill| {
+                let content = variables::substitute(&skill.content, &values)
+                    .with_context(|| format!("Failed to render skill `{}`", skill.name))?;
+                Ok((skills_dir.join(format!("{}.md", skill.name)), content))
+            })
+            .collect()
     }
 
-    fn install_tools(&self, agent: &AgentConfig) -> Result<()> {
+    /// Render this agent's own MCP server fragment (`{"mcpServers": {...}}`
+    /// with just its own entries, substituted), not the full merged config --
+    /// that's merged in fresh at write time by `tools::merge_fragment`, so
+    /// reviewing this fragment never exposes another agent's secrets
+    fn render_tools(&self, agent: &AgentConfig) -> Result<Option<(PathBuf, Value)>> {
         if agent.mcp.is_empty() {
-            return Ok(());
+            return Ok(None);
         }
 
         let config_path = self.get_config_path()?;
+        let mut servers = serde_json::Map::new();
 
-        // Load existing config or create new one
-        let mut config: Value = if config_path.exists() {
-            let content = fs::read_to_string(&config_path)?;
-            serde_json::from_str(&content).unwrap_or_else(|_| json!({}))
-        } else {
-            json!({})
-        };
-
-        // Ensure mcpServers object exists
-        if config.get("mcpServers").is_none() {
-            config["mcpServers"] = json!({});
-        }
+        let vars_file = self.get_vars_file(&agent.name)?;
+        let env_texts: Vec<&str> = agent
+            .mcp
+            .iter()
+            .flat_map(|tool| tool.env.values().map(|v| v.as_str()))
+            .collect();
+        let values = variables::resolve(agent, &env_texts, &vars_file)?;
 
-        // Add each MCP tool
         for tool in &agent.mcp {
+            let mut env = tool.env.clone();
+            for (key, value) in env.iter_mut() {
+                *value = variables::substitute(value, &values)
+                    .with_context(|| format!("Failed to render env `{}` for MCP tool `{}`", key, tool.name))?;
+            }
+
             let tool_config = json!({
                 "command": tool.command,
                 "args": tool.args,
-                "env": tool.env
+                "env": env
             });
-            config["mcpServers"][&tool.name] = tool_config;
+            servers.insert(tool.name.clone(), tool_config);
 
-            // Check for setup URL (API key requirement)
             if let Some(url) = &tool.setup_url {
                 println!("\n  {} Setup required for MCP tool '{}'", "ℹ".blue().bold(), tool.name.bold());
                 println!("  {} Get your API key here: {}", "→".cyan(), url.underline().blue());
             }
         }
 
-        // Ensure parent directory exists
-        if let Some(parent) = config_path.parent() {
-            fs::create_dir_all(parent)?;
+        Ok(Some((config_path, json!({ "mcpServers": servers }))))
+    }
+}
+
+impl Installer for CodexInstaller {
+    fn render(&self, agent: &AgentConfig) -> Result<Vec<PlannedFile>> {
+        let mut files = Vec::new();
+
+        let (path, contents) = self.render_identity(agent)?;
+        files.push(PlannedFile { path, contents, merge_mcp_fragment: false });
+
+        for (path, contents) in self.render_skills(agent)? {
+            files.push(PlannedFile { path, contents, merge_mcp_fragment: false });
         }
 
-        // Write the updated config
-        fs::write(&config_path, serde_json::to_string_pretty(&config)?)?;
+        if let Some((path, fragment)) = self.render_tools(agent)? {
+            files.push(PlannedFile {
+                path,
+                contents: serde_json::to_string_pretty(&fragment)?,
+                merge_mcp_fragment: true,
+            });
+        }
 
-        Ok(())
+        Ok(files)
+    }
+
+    fn reconcile_tools(&self, agent: &AgentConfig) -> Result<()> {
+        tools::reconcile(&self.get_config_path()?, &self.get_agents_dir()?, agent)
+    }
+
+    fn install_rag(&self, agent: &AgentConfig) -> Result<()> {
+        if agent.rag.is_empty() {
+            return Ok(());
+        }
+
+        rag::install_sources(&self.get_rag_dir(&agent.name)?, &agent.rag)
     }
 
     fn uninstall(&self, agent_name: &str) -> Result<()> {
@@ -158,6 +214,23 @@ impl Installer for CodexInstaller {
             fs::remove_dir_all(&skills_dir)?;
         }
 
+        // Remove RAG store
+        let rag_dir = self.get_rag_dir(agent_name)?;
+        if rag_dir.exists() {
+            fs::remove_dir_all(&rag_dir)?;
+        }
+
+        // Remove only the MCP servers this agent owns, and only if no other
+        // installed agent still claims them
+        tools::remove_agent(&self.get_config_path()?, &self.get_agents_dir()?, agent_name)?;
+
+        // Remove persisted variable answers, which can hold values typed in
+        // as MCP env credentials
+        let vars_file = self.get_vars_file(agent_name)?;
+        if vars_file.exists() {
+            fs::remove_file(&vars_file)?;
+        }
+
         Ok(())
     }
 }
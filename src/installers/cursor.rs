@@ -0,0 +1,241 @@
+//! Cursor Installer
+//!
+//! Installs agent configurations into Cursor's native format.
+//!
+//! Output structure:
+//! - ~/.cursor/agents/{name}.json - Agent as a JSON document (Cursor has no
+//!   markdown-frontmatter agent format)
+//! - ~/.cursor/skills/{name}/{skill}.md - Skills as Markdown files
+//! - ~/.cursor/skills/{name}/rag/ - RAG source documents (no native RAG store,
+//!   so documents are copied alongside the skills instead)
+//! - ~/.cursor/mcp.json - MCP tool configuration
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde_json::{json, Value};
+use std::fs;
+use std::path::PathBuf;
+
+use super::{rag, tools, variables};
+use super::{Installer, PlannedFile};
+use crate::core::agent::AgentConfig;
+use crate::utils::paths;
+
+/// Installer for Cursor
+pub struct CursorInstaller {
+    /// Whether to install globally
+    global: bool,
+}
+
+impl CursorInstaller {
+    pub fn new(global: bool) -> Self {
+        Self { global }
+    }
+
+    /// Get the base directory for Cursor configuration
+    fn get_base_dir(&self) -> Result<PathBuf> {
+        paths::cursor_config_dir()
+            .context("Could not find Cursor configuration directory")
+    }
+
+    /// Get the agents directory
+    fn get_agents_dir(&self) -> Result<PathBuf> {
+        Ok(self.get_base_dir()?.join("agents"))
+    }
+
+    /// Get the Cursor MCP config path
+    fn get_config_path(&self) -> Result<PathBuf> {
+        Ok(self.get_base_dir()?.join("mcp.json"))
+    }
+
+    /// Get the path to an agent's persisted variable answers
+    fn get_vars_file(&self, agent_name: &str) -> Result<PathBuf> {
+        Ok(variables::vars_file_path(&self.get_agents_dir()?, agent_name))
+    }
+
+    /// Generate the agent JSON document. Unlike Codex/Claude's markdown
+    /// frontmatter, Cursor reads agent identity as plain JSON, so only the
+    /// fields the user actually set are included.
+    fn generate_agent_json(agent: &AgentConfig, system_prompt: &str) -> Value {
+        let params = agent.identity.parameters.as_ref();
+        let model = params
+            .and_then(|p| p.model_id.as_deref())
+            .or(agent.identity.model.as_deref());
+
+        let mut doc = json!({
+            "name": agent.name,
+            "description": agent.description,
+            "systemPrompt": system_prompt,
+        });
+
+        if let Some(icon) = &agent.identity.icon {
+            doc["icon"] = json!(icon);
+        }
+        if let Some(model) = model {
+            doc["model"] = json!(model);
+        }
+
+        if let Some(params) = params {
+            let mut parameters = serde_json::Map::new();
+            if let Some(temperature) = params.temperature {
+                parameters.insert("temperature".to_string(), json!(temperature));
+            }
+            if let Some(top_p) = params.top_p {
+                parameters.insert("topP".to_string(), json!(top_p));
+            }
+            if let Some(reasoning_effort) = &params.reasoning_effort {
+                parameters.insert("reasoningEffort".to_string(), json!(reasoning_effort));
+            }
+            if let Some(max_tokens) = params.max_tokens {
+                parameters.insert("maxTokens".to_string(), json!(max_tokens));
+            }
+            if !parameters.is_empty() {
+                doc["parameters"] = Value::Object(parameters);
+            }
+        }
+
+        doc
+    }
+
+    /// Render the agent JSON file's path and final (variable-substituted) content
+    fn render_identity(&self, agent: &AgentConfig) -> Result<(PathBuf, Value)> {
+        let vars_file = self.get_vars_file(&agent.name)?;
+        let values = variables::resolve(agent, &[&agent.identity.system_prompt], &vars_file)?;
+        let system_prompt = variables::substitute(&agent.identity.system_prompt, &values)
+            .context("Failed to render agent system prompt")?;
+
+        let agent_file = self.get_agents_dir()?.join(format!("{}.json", agent.name));
+        Ok((agent_file, Self::generate_agent_json(agent, &system_prompt)))
+    }
+
+    /// Render each skill file's path and final (variable-substituted) content
+    fn render_skills(&self, agent: &AgentConfig) -> Result<Vec<(PathBuf, String)>> {
+        if agent.skills.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let skills_dir = self.get_base_dir()?.join("skills").join(&agent.name);
+        let vars_file = self.get_vars_file(&agent.name)?;
+        let skill_texts: Vec<&str> = agent.skills.iter().map(|s| s.content.as_str()).collect();
+        let values = variables::resolve(agent, &skill_texts, &vars_file)?;
+
+        agent
+            .skills
+            .iter()
+            .map(|skill| {
+                let content = variables::substitute(&skill.content, &values)
+                    .with_context(|| format!("Failed to render skill `{}`", skill.name))?;
+                Ok((skills_dir.join(format!("{}.md", skill.name)), content))
+            })
+            .collect()
+    }
+
+    /// Render this agent's own MCP server fragment (`{"mcpServers": {...}}`
+    /// with just its own entries, substituted), not the full merged config --
+    /// that's merged in fresh at write time by `tools::merge_fragment`, so
+    /// reviewing this fragment never exposes another agent's secrets
+    fn render_tools(&self, agent: &AgentConfig) -> Result<Option<(PathBuf, Value)>> {
+        if agent.mcp.is_empty() {
+            return Ok(None);
+        }
+
+        let config_path = self.get_config_path()?;
+        let mut servers = serde_json::Map::new();
+
+        let vars_file = self.get_vars_file(&agent.name)?;
+        let env_texts: Vec<&str> = agent
+            .mcp
+            .iter()
+            .flat_map(|tool| tool.env.values().map(|v| v.as_str()))
+            .collect();
+        let values = variables::resolve(agent, &env_texts, &vars_file)?;
+
+        for tool in &agent.mcp {
+            let mut env = tool.env.clone();
+            for (key, value) in env.iter_mut() {
+                *value = variables::substitute(value, &values)
+                    .with_context(|| format!("Failed to render env `{}` for MCP tool `{}`", key, tool.name))?;
+            }
+
+            let tool_config = json!({
+                "command": tool.command,
+                "args": tool.args,
+                "env": env
+            });
+            servers.insert(tool.name.clone(), tool_config);
+
+            if let Some(url) = &tool.setup_url {
+                println!("\n  {} Setup required for MCP tool '{}'", "ℹ".blue().bold(), tool.name.bold());
+                println!("  {} Get your API key here: {}", "→".cyan(), url.underline().blue());
+            }
+        }
+
+        Ok(Some((config_path, json!({ "mcpServers": servers }))))
+    }
+
+}
+
+impl Installer for CursorInstaller {
+    fn render(&self, agent: &AgentConfig) -> Result<Vec<PlannedFile>> {
+        let mut files = Vec::new();
+
+        let (path, doc) = self.render_identity(agent)?;
+        files.push(PlannedFile {
+            path,
+            contents: serde_json::to_string_pretty(&doc)?,
+            merge_mcp_fragment: false,
+        });
+
+        for (path, contents) in self.render_skills(agent)? {
+            files.push(PlannedFile { path, contents, merge_mcp_fragment: false });
+        }
+
+        if let Some((path, fragment)) = self.render_tools(agent)? {
+            files.push(PlannedFile {
+                path,
+                contents: serde_json::to_string_pretty(&fragment)?,
+                merge_mcp_fragment: true,
+            });
+        }
+
+        Ok(files)
+    }
+
+    fn reconcile_tools(&self, agent: &AgentConfig) -> Result<()> {
+        tools::reconcile(&self.get_config_path()?, &self.get_agents_dir()?, agent)
+    }
+
+    fn install_rag(&self, agent: &AgentConfig) -> Result<()> {
+        if agent.rag.is_empty() {
+            return Ok(());
+        }
+
+        // Cursor has no native RAG store, so documents are copied next to the
+        // agent's skills instead
+        let rag_dir = self.get_base_dir()?.join("skills").join(&agent.name).join("rag");
+        rag::install_sources(&rag_dir, &agent.rag)
+    }
+
+    fn uninstall(&self, agent_name: &str) -> Result<()> {
+        let agent_file = self.get_agents_dir()?.join(format!("{}.json", agent_name));
+        if agent_file.exists() {
+            fs::remove_file(&agent_file)?;
+        }
+
+        let skills_dir = self.get_base_dir()?.join("skills").join(agent_name);
+        if skills_dir.exists() {
+            fs::remove_dir_all(&skills_dir)?;
+        }
+
+        // Remove only the MCP servers this agent owns, and only if no other
+        // installed agent still claims them
+        tools::remove_agent(&self.get_config_path()?, &self.get_agents_dir()?, agent_name)?;
+
+        let vars_file = self.get_vars_file(agent_name)?;
+        if vars_file.exists() {
+            fs::remove_file(&vars_file)?;
+        }
+
+        Ok(())
+    }
+}
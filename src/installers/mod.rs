@@ -5,8 +5,18 @@
 mod claude;
 mod cursor;
 mod codex;
+mod rag;
+mod tools;
+mod variables;
 
-use anyhow::Result;
+use std::io::Write as _;
+use std::path::PathBuf;
+use std::process;
+
+#[cfg(unix)]
+use std::os::unix::fs::OpenOptionsExt;
+
+use anyhow::{bail, Context, Result};
 
 pub use claude::ClaudeInstaller;
 pub use cursor::CursorInstaller;
@@ -33,16 +43,35 @@ impl Target {
     }
 }
 
+/// A file an installer intends to write, rendered ahead of time so it can be
+/// reviewed (or, in future, dry-run printed) before anything touches disk
+pub struct PlannedFile {
+    pub path: PathBuf,
+    pub contents: String,
+    /// When set, `contents` is only this agent's own MCP tool fragment (a
+    /// `{"mcpServers": {...}}` document with just its own entries), not the
+    /// full merged config -- reviewing the whole merged config would expose
+    /// every other already-installed agent's secrets just to review this
+    /// agent's own. `commit` merges the fragment into the on-disk config
+    /// fresh at write time instead of overwriting it.
+    pub merge_mcp_fragment: bool,
+}
+
 /// Installer trait - the adapter pattern for different editors
 pub trait Installer: Send + Sync {
-    /// Install the agent's identity (system prompt)
-    fn install_identity(&self, agent: &AgentConfig) -> Result<()>;
+    /// Render the identity markdown, skill files, and MCP tool config fragment
+    /// this installer would write for `agent`, without touching disk
+    fn render(&self, agent: &AgentConfig) -> Result<Vec<PlannedFile>>;
 
-    /// Install the agent's skills (knowledge base)
-    fn install_skills(&self, agent: &AgentConfig) -> Result<()>;
+    /// Reconcile the MCP ownership sidecar against `agent`'s current `mcp`
+    /// list: prune any server this agent previously owned but no longer
+    /// declares (and that no other installed agent still claims), and record
+    /// the current ownership set. Does not add or write the tool config for
+    /// servers the agent still owns — that happens via `render`/`commit`.
+    fn reconcile_tools(&self, agent: &AgentConfig) -> Result<()>;
 
-    /// Install the agent's MCP tools
-    fn install_tools(&self, agent: &AgentConfig) -> Result<()>;
+    /// Install the agent's RAG knowledge base
+    fn install_rag(&self, agent: &AgentConfig) -> Result<()>;
 
     /// Uninstall an agent by name
     fn uninstall(&self, agent_name: &str) -> Result<()>;
@@ -56,3 +85,97 @@ pub fn get_installer(target: Target, global: bool) -> Box<dyn Installer> {
         Target::Codex => Box::new(CodexInstaller::new(global)),
     }
 }
+
+/// Install an agent end-to-end: render the identity markdown/skills/tool
+/// config and write them (optionally letting the user review each one in
+/// `$EDITOR`/`$VISUAL` first), then reconcile MCP ownership and install the
+/// RAG store. This is the entry point CLI commands should call instead of
+/// invoking the per-phase `Installer` methods directly, since it's the only
+/// path that keeps MCP ownership bookkeeping in sync with what actually got
+/// written.
+///
+/// Reconciliation runs after `commit` succeeds, not before: it prunes
+/// servers this agent no longer declares and rewrites the ownership
+/// sidecar, and doing that before the new tool config is confirmed on disk
+/// would leave the sidecar claiming ownership of entries that never
+/// actually got written if `commit` failed partway through (e.g. the user's
+/// editor exiting non-zero during review).
+pub fn install(installer: &dyn Installer, agent: &AgentConfig, review: bool) -> Result<()> {
+    let files = installer.render(agent)?;
+    commit(files, review)?;
+    installer.reconcile_tools(agent)?;
+    installer.install_rag(agent)?;
+    Ok(())
+}
+
+/// Write rendered files to disk, reviewing each one in the user's editor first
+/// when `review` is set
+fn commit(files: Vec<PlannedFile>, review: bool) -> Result<()> {
+    for (index, file) in files.into_iter().enumerate() {
+        let contents = if review {
+            review_in_editor(&file.contents, index)?
+        } else {
+            file.contents
+        };
+
+        let contents = if file.merge_mcp_fragment {
+            tools::merge_fragment(&file.path, &contents)?
+        } else {
+            contents
+        };
+
+        if let Some(parent) = file.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&file.path, contents)
+            .with_context(|| format!("Failed to write {}", file.path.display()))?;
+    }
+
+    Ok(())
+}
+
+/// Open `contents` in `$VISUAL`/`$EDITOR` (falling back to `vi`) via a temp file
+/// and return whatever the user saved
+fn review_in_editor(contents: &str, index: usize) -> Result<String> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+
+    // Created exclusively (fails if the path already exists, so a symlink or
+    // file planted ahead of time at a guessed path is never followed/reused)
+    // and 0o600 (not the world-readable default), since `contents` can hold
+    // this agent's substituted MCP secrets
+    let tmp_path = std::env::temp_dir().join(format!("apm-review-{}-{}.tmp", process::id(), index));
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    let mut tmp_file = options
+        .open(&tmp_path)
+        .with_context(|| format!("Failed to create {}", tmp_path.display()))?;
+    tmp_file.write_all(contents.as_bytes())?;
+    drop(tmp_file);
+
+    let status = process::Command::new(&editor)
+        .arg(&tmp_path)
+        .status()
+        .with_context(|| format!("Failed to launch editor `{}`", editor));
+    let status = match status {
+        Ok(status) => status,
+        Err(err) => {
+            let _ = std::fs::remove_file(&tmp_path);
+            return Err(err);
+        }
+    };
+
+    if !status.success() {
+        let _ = std::fs::remove_file(&tmp_path);
+        bail!("Editor `{}` exited with a non-zero status", editor);
+    }
+
+    let edited = std::fs::read_to_string(&tmp_path)
+        .with_context(|| format!("Failed to read back {}", tmp_path.display()))?;
+    let _ = std::fs::remove_file(&tmp_path);
+
+    Ok(edited)
+}
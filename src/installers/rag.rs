@@ -0,0 +1,128 @@
+//! RAG Source Handling
+//!
+//! Shared helpers for materializing an agent's `rag` source documents to disk,
+//! whether that's a dedicated RAG store (Codex) or a fallback location next to
+//! the agent's skills (editors with no native RAG support).
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+/// One entry in a `rag.json` manifest, recording enough to detect changed
+/// sources on a subsequent install
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RagManifestEntry {
+    pub source: PathBuf,
+    pub size: u64,
+    pub hash: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct RagManifest {
+    #[serde(default)]
+    pub entries: Vec<RagManifestEntry>,
+}
+
+/// Expand a RAG source entry (a literal path or a single-level `*` glob) into
+/// the concrete files it refers to
+pub fn expand_source(pattern: &str) -> Result<Vec<PathBuf>> {
+    let path = Path::new(pattern);
+    if !pattern.contains('*') {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    let dir = path.parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or_else(|| Path::new("."));
+    let file_pattern = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .with_context(|| format!("Invalid RAG source glob `{}`", pattern))?;
+    let (prefix, suffix) = file_pattern
+        .split_once('*')
+        .with_context(|| format!("Unsupported RAG source glob `{}`", pattern))?;
+
+    let mut matches = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("Failed to read directory `{}`", dir.display()))? {
+        let entry = entry?;
+        let file_name = entry.file_name();
+        let file_name = file_name.to_string_lossy();
+        if file_name.starts_with(prefix) && file_name.ends_with(suffix) {
+            matches.push(entry.path());
+        }
+    }
+    matches.sort();
+    Ok(matches)
+}
+
+fn hash_content(bytes: &[u8]) -> String {
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// Materialize every file matched by `sources` into `dest_dir`, skipping any
+/// file whose content hash matches what `dest_dir`'s existing `rag.json`
+/// manifest recorded for it, deleting the destination copy of any previously
+/// installed source no longer present in `sources`, and rewriting the
+/// manifest with the current state
+pub fn install_sources(dest_dir: &Path, sources: &[crate::core::agent::RagSource]) -> Result<()> {
+    fs::create_dir_all(dest_dir)?;
+
+    let manifest_path = dest_dir.join("rag.json");
+    let previous: RagManifest = if manifest_path.exists() {
+        let content = fs::read_to_string(&manifest_path)?;
+        serde_json::from_str(&content).unwrap_or_default()
+    } else {
+        RagManifest::default()
+    };
+
+    let mut entries = Vec::new();
+    let mut current_sources = std::collections::HashSet::new();
+    for source in sources {
+        for file in expand_source(&source.path)? {
+            let bytes = fs::read(&file)
+                .with_context(|| format!("Failed to read RAG source `{}`", file.display()))?;
+            let hash = hash_content(&bytes);
+
+            let unchanged = previous
+                .entries
+                .iter()
+                .any(|e| e.source == file && e.hash == hash);
+
+            let dest_name = file
+                .file_name()
+                .with_context(|| format!("RAG source `{}` has no file name", file.display()))?;
+            let dest = dest_dir.join(dest_name);
+
+            if !unchanged || !dest.exists() {
+                fs::write(&dest, &bytes)?;
+            }
+
+            current_sources.insert(file.clone());
+            entries.push(RagManifestEntry {
+                source: file,
+                size: bytes.len() as u64,
+                hash,
+            });
+        }
+    }
+
+    // Drop the destination copy of any source the agent no longer declares,
+    // so it doesn't silently persist until a full uninstall
+    for stale in previous.entries.iter().filter(|e| !current_sources.contains(&e.source)) {
+        if let Some(dest_name) = stale.source.file_name() {
+            let dest = dest_dir.join(dest_name);
+            if dest.exists() {
+                fs::remove_file(&dest)?;
+            }
+        }
+    }
+
+    let manifest = RagManifest { entries };
+    fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?)?;
+
+    Ok(())
+}
@@ -0,0 +1,184 @@
+//! MCP Tool Ownership
+//!
+//! Shared helpers for tracking which MCP servers each installed agent
+//! introduced into an editor's shared tool config file, so a server stays
+//! configured as long as any installed agent still claims it and is pruned
+//! once none do.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::core::agent::AgentConfig;
+
+/// Sidecar recording which MCP server names an agent introduced, so `uninstall`
+/// only removes a server once no other installed agent still claims it
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct AgentToolsManifest {
+    #[serde(default)]
+    servers: Vec<String>,
+}
+
+/// Get the path to the sidecar file recording which MCP servers an agent owns
+fn tools_file_path(agents_dir: &Path, agent_name: &str) -> PathBuf {
+    agents_dir.join(format!("{}.tools.json", agent_name))
+}
+
+/// Read every `*.tools.json` sidecar in `agents_dir` so ownership of a given
+/// server name can be checked across all installed agents
+fn all_owned_servers(agents_dir: &Path, except_agent: &str) -> Result<HashSet<String>> {
+    let mut owned = HashSet::new();
+
+    if !agents_dir.exists() {
+        return Ok(owned);
+    }
+
+    for entry in fs::read_dir(agents_dir)? {
+        let entry = entry?;
+        let file_name = entry.file_name();
+        let file_name = file_name.to_string_lossy();
+        let Some(agent_name) = file_name.strip_suffix(".tools.json") else {
+            continue;
+        };
+        if agent_name == except_agent {
+            continue;
+        }
+
+        let content = fs::read_to_string(entry.path())?;
+        let manifest: AgentToolsManifest = serde_json::from_str(&content).unwrap_or_default();
+        owned.extend(manifest.servers);
+    }
+
+    Ok(owned)
+}
+
+/// Remove `names` from `config_path`'s `mcpServers` object, skipping any name
+/// still present in `still_claimed`, and drop the `mcpServers` key entirely
+/// once it's empty
+fn prune_from_config(config_path: &Path, names: &[&String], still_claimed: &HashSet<String>) -> Result<()> {
+    if !config_path.exists() {
+        return Ok(());
+    }
+
+    let content = fs::read_to_string(config_path)?;
+    let mut config: Value = serde_json::from_str(&content).unwrap_or_else(|_| json!({}));
+
+    if let Some(servers) = config.get_mut("mcpServers").and_then(Value::as_object_mut) {
+        for name in names {
+            if !still_claimed.contains(*name) {
+                servers.remove(*name);
+            }
+        }
+        if servers.is_empty() {
+            if let Some(root) = config.as_object_mut() {
+                root.remove("mcpServers");
+            }
+        }
+    }
+
+    fs::write(config_path, serde_json::to_string_pretty(&config)?)?;
+    Ok(())
+}
+
+/// Merge a rendered `{"mcpServers": {...}}` fragment (an agent's own MCP
+/// servers, substituted) into the config at `config_path` -- read fresh, not
+/// the possibly-stale copy the fragment was rendered against -- and return
+/// the merged document pretty-printed. Lets `commit` write (and review)
+/// only one agent's own fragment instead of the full merged config, which
+/// may hold every other already-installed agent's secrets
+pub fn merge_fragment(config_path: &Path, fragment: &str) -> Result<String> {
+    let fragment: Value = serde_json::from_str(fragment)?;
+    let fragment_servers = fragment
+        .get("mcpServers")
+        .and_then(Value::as_object)
+        .cloned()
+        .unwrap_or_default();
+
+    let mut config: Value = if config_path.exists() {
+        let content = fs::read_to_string(config_path)?;
+        serde_json::from_str(&content).unwrap_or_else(|_| json!({}))
+    } else {
+        json!({})
+    };
+
+    if config.get("mcpServers").is_none() {
+        config["mcpServers"] = json!({});
+    }
+    if let Some(servers) = config.get_mut("mcpServers").and_then(Value::as_object_mut) {
+        for (name, value) in fragment_servers {
+            servers.insert(name, value);
+        }
+    }
+
+    Ok(serde_json::to_string_pretty(&config)?)
+}
+
+/// Reconcile the MCP ownership sidecar against `agent`'s current `mcp` list:
+/// prune any server this agent previously owned but no longer declares (and
+/// that no other installed agent still claims) from `config_path`, and
+/// record the current ownership set in the `{name}.tools.json` sidecar under
+/// `agents_dir`. Does not add or write config for servers the agent still
+/// owns -- that happens via `render`/`commit`.
+pub fn reconcile(config_path: &Path, agents_dir: &Path, agent: &AgentConfig) -> Result<()> {
+    let now_owned: HashSet<String> = agent.mcp.iter().map(|tool| tool.name.clone()).collect();
+
+    let tools_file = tools_file_path(agents_dir, &agent.name);
+    let previously_owned: Vec<String> = if tools_file.exists() {
+        let content = fs::read_to_string(&tools_file)?;
+        serde_json::from_str::<AgentToolsManifest>(&content)
+            .unwrap_or_default()
+            .servers
+    } else {
+        Vec::new()
+    };
+
+    let dropped: Vec<&String> = previously_owned
+        .iter()
+        .filter(|name| !now_owned.contains(*name))
+        .collect();
+
+    if !dropped.is_empty() {
+        let still_claimed = all_owned_servers(agents_dir, &agent.name)?;
+        prune_from_config(config_path, &dropped, &still_claimed)?;
+    }
+
+    if now_owned.is_empty() {
+        if tools_file.exists() {
+            fs::remove_file(&tools_file)?;
+        }
+        return Ok(());
+    }
+
+    if let Some(parent) = tools_file.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let owned = AgentToolsManifest {
+        servers: now_owned.into_iter().collect(),
+    };
+    fs::write(&tools_file, serde_json::to_string_pretty(&owned)?)?;
+
+    Ok(())
+}
+
+/// Remove every MCP server `agent_name` owns from `config_path` (unless
+/// another installed agent still claims it) and delete its ownership
+/// sidecar under `agents_dir`. Used by `uninstall`.
+pub fn remove_agent(config_path: &Path, agents_dir: &Path, agent_name: &str) -> Result<()> {
+    let tools_file = tools_file_path(agents_dir, agent_name);
+    if !tools_file.exists() {
+        return Ok(());
+    }
+
+    let content = fs::read_to_string(&tools_file)?;
+    let owned: AgentToolsManifest = serde_json::from_str(&content).unwrap_or_default();
+    let still_claimed = all_owned_servers(agents_dir, agent_name)?;
+    let names: Vec<&String> = owned.servers.iter().collect();
+    prune_from_config(config_path, &names, &still_claimed)?;
+
+    fs::remove_file(&tools_file)?;
+    Ok(())
+}
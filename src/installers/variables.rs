@@ -0,0 +1,170 @@
+//! Variable Substitution
+//!
+//! Agents can declare placeholders via `AgentConfig.variables` and reference them as
+//! `{{name}}` inside the system prompt, skill bodies, and MCP `env` values. Before any
+//! installer writes an output to disk, it resolves the placeholders that output
+//! actually references against a per-agent answers file, prompting interactively for
+//! anything missing, then substitutes. This lets one agent definition be specialized
+//! per machine/user without editing the source.
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::core::agent::AgentConfig;
+
+/// Answers persisted for an agent so reinstalls don't re-prompt.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StoredVariables {
+    #[serde(default)]
+    values: HashMap<String, String>,
+}
+
+/// Path to the per-agent variables file, e.g. `~/.codex/agents/{name}.vars.json`.
+pub fn vars_file_path(agents_dir: &Path, agent_name: &str) -> PathBuf {
+    agents_dir.join(format!("{}.vars.json", agent_name))
+}
+
+/// Resolve every `{{name}}` placeholder referenced in `texts`, prompting for any value
+/// not already stored in `vars_file`, and persisting newly answered values.
+pub fn resolve(agent: &AgentConfig, texts: &[&str], vars_file: &Path) -> Result<HashMap<String, String>> {
+    let mut stored = load_stored(vars_file)?;
+
+    let mut referenced = std::collections::BTreeSet::new();
+    for text in texts {
+        for name in find_placeholders(text) {
+            referenced.insert(name);
+        }
+    }
+
+    let mut changed = false;
+    for name in &referenced {
+        if stored.contains_key(name) {
+            continue;
+        }
+
+        let declared = agent.variables.iter().find(|v| &v.name == name);
+        let (prompt, default) = match declared {
+            Some(v) => (v.description.as_str(), v.default.as_deref()),
+            None => (name.as_str(), None),
+        };
+
+        let value = prompt_for_value(name, prompt, default)?;
+        stored.insert(name.clone(), value);
+        changed = true;
+    }
+
+    if changed {
+        save_stored(vars_file, &stored)?;
+    }
+
+    Ok(stored)
+}
+
+/// Substitute all `{{name}}` placeholders in `text`, failing if any remain undefined.
+///
+/// Uses the same whitespace-tolerant tokenizer as `find_placeholders` (so
+/// `{{name}}` and `{{ name }}` are treated as the same reference) rather than
+/// a literal string replace, so the two functions never disagree about what
+/// counts as "defined".
+pub fn substitute(text: &str, values: &HashMap<String, String>) -> Result<String> {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    loop {
+        let Some(start) = rest.find("{{") else {
+            result.push_str(rest);
+            break;
+        };
+        result.push_str(&rest[..start]);
+
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            result.push_str(&rest[start..]);
+            break;
+        };
+
+        let name = after[..end].trim();
+        if name.is_empty() {
+            result.push_str(&rest[start..start + 4 + end]);
+        } else {
+            match values.get(name) {
+                Some(value) => result.push_str(value),
+                None => bail!("undefined variable `{{{{{}}}}}` left in rendered output", name),
+            }
+        }
+
+        rest = &after[end + 2..];
+    }
+
+    Ok(result)
+}
+
+fn find_placeholders(text: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        if let Some(end) = after.find("}}") {
+            let name = after[..end].trim();
+            if !name.is_empty() {
+                names.push(name.to_string());
+            }
+            rest = &after[end + 2..];
+        } else {
+            break;
+        }
+    }
+    names
+}
+
+fn load_stored(vars_file: &Path) -> Result<HashMap<String, String>> {
+    if !vars_file.exists() {
+        return Ok(HashMap::new());
+    }
+
+    let content = std::fs::read_to_string(vars_file)
+        .with_context(|| format!("Failed to read {}", vars_file.display()))?;
+    let stored: StoredVariables = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", vars_file.display()))?;
+    Ok(stored.values)
+}
+
+fn save_stored(vars_file: &Path, values: &HashMap<String, String>) -> Result<()> {
+    if let Some(parent) = vars_file.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let stored = StoredVariables {
+        values: values.clone(),
+    };
+    std::fs::write(vars_file, serde_json::to_string_pretty(&stored)?)
+        .with_context(|| format!("Failed to write {}", vars_file.display()))
+}
+
+fn prompt_for_value(name: &str, description: &str, default: Option<&str>) -> Result<String> {
+    match default {
+        Some(default) => print!("  {} ({}) [{}]: ", name, description, default),
+        None => print!("  {} ({}): ", name, description),
+    }
+    io::stdout().flush().ok();
+
+    let mut line = String::new();
+    io::stdin()
+        .lock()
+        .read_line(&mut line)
+        .with_context(|| format!("Failed to read a value for variable `{}`", name))?;
+
+    let answer = line.trim();
+    if answer.is_empty() {
+        if let Some(default) = default {
+            return Ok(default.to_string());
+        }
+        bail!("No value provided for required variable `{{{{{}}}}}`", name);
+    }
+
+    Ok(answer.to_string())
+}
@@ -0,0 +1,3 @@
+//! Utility Module
+
+pub mod paths;
@@ -0,0 +1,29 @@
+//! Editor Configuration Paths
+//!
+//! Resolves each editor's configuration directory from the user's home
+//! directory. Installers are responsible for joining on whatever subpaths
+//! they need beneath these roots.
+
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+
+fn home_dir() -> Result<PathBuf> {
+    std::env::var_os("HOME")
+        .map(PathBuf::from)
+        .context("Could not determine the home directory (HOME is not set)")
+}
+
+/// Get the base directory for Codex configuration (`~/.codex`)
+pub fn codex_config_dir() -> Result<PathBuf> {
+    Ok(home_dir()?.join(".codex"))
+}
+
+/// Get the base directory for Claude Code configuration (`~/.claude`)
+pub fn claude_config_dir() -> Result<PathBuf> {
+    Ok(home_dir()?.join(".claude"))
+}
+
+/// Get the base directory for Cursor configuration (`~/.cursor`)
+pub fn cursor_config_dir() -> Result<PathBuf> {
+    Ok(home_dir()?.join(".cursor"))
+}